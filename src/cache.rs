@@ -0,0 +1,193 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::ratelimit::RateLimiter;
+
+/// An on-disk cache entry for one URL: the fetched body plus enough metadata
+/// to judge freshness and, once stale, revalidate cheaply.
+#[derive(Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub body: String,
+    pub fetched_at: u64,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        now.saturating_sub(self.fetched_at) < ttl.as_secs()
+    }
+
+    /// Whether this entry carries a validator we can revalidate with.
+    pub fn has_validator(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+/// A simple on-disk response cache keyed by the full URL.
+pub struct Cache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf, ttl_secs: u64) -> Cache {
+        Cache {
+            dir,
+            ttl: Duration::from_secs(ttl_secs),
+        }
+    }
+
+    /// Load the cached entry for `url`, if one exists and can be read.
+    pub fn load(&self, url: &str) -> Option<CacheEntry> {
+        let path = self.path_for(url);
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Load the entry only if it's still within the configured TTL.
+    pub fn load_fresh(&self, url: &str) -> Option<CacheEntry> {
+        self.load(url).filter(|entry| entry.is_fresh(self.ttl))
+    }
+
+    pub fn store(&self, url: &str, entry: &CacheEntry) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string_pretty(entry).map_err(std::io::Error::other)?;
+        std::fs::write(self.path_for(url), json)
+    }
+
+    fn path_for(&self, url: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+}
+
+enum FetchOutcome {
+    Fetched { body: String, etag: Option<String>, last_modified: Option<String> },
+    NotModified,
+}
+
+/// Fetch `url`'s body, consulting `cache` first: a fresh entry is returned
+/// without touching the network; a stale entry with a validator is
+/// revalidated with a conditional request and reused on `304 Not Modified`.
+pub async fn fetch_cached(
+    client: &Client,
+    url: &Url,
+    cache: Option<&Cache>,
+    limiter: &RateLimiter,
+) -> Result<String> {
+    let Some(cache) = cache else {
+        return match fetch_fresh(client, url, None, limiter).await? {
+            FetchOutcome::Fetched { body, .. } => Ok(body),
+            // We never sent a conditional header, so this 304 is the
+            // server's doing (a CDN or proxy emitting one unprompted), not
+            // something we can make sense of - fail the request rather than
+            // crash the whole run.
+            FetchOutcome::NotModified => Err(anyhow!(
+                "{} returned 304 Not Modified without a conditional request being sent",
+                url
+            )),
+        };
+    };
+
+    if let Some(entry) = cache.load_fresh(url.as_str()) {
+        return Ok(entry.body);
+    }
+
+    let stale = cache.load(url.as_str());
+    let revalidate = stale.as_ref().filter(|entry| entry.has_validator());
+
+    let (body, etag, last_modified) = match fetch_fresh(client, url, revalidate, limiter).await? {
+        FetchOutcome::NotModified => match stale {
+            Some(entry) => (entry.body, entry.etag, entry.last_modified),
+            // Same as above: an unsolicited 304 with nothing cached to
+            // reuse. Treat it as a fetch failure instead of panicking.
+            None => {
+                return Err(anyhow!(
+                    "{} returned 304 Not Modified but no cached entry exists to reuse",
+                    url
+                ))
+            }
+        },
+        FetchOutcome::Fetched { body, etag, last_modified } => (body, etag, last_modified),
+    };
+
+    let fetched_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let entry = CacheEntry { body: body.clone(), fetched_at, etag, last_modified };
+    if let Err(e) = cache.store(url.as_str(), &entry) {
+        eprintln!("Warning: failed to write cache entry for {}: {}", url, e);
+    }
+
+    Ok(body)
+}
+
+/// Perform the actual HTTP GET, optionally sending conditional-request
+/// headers derived from `revalidate`.
+async fn fetch_fresh(
+    client: &Client,
+    url: &Url,
+    revalidate: Option<&CacheEntry>,
+    limiter: &RateLimiter,
+) -> Result<FetchOutcome> {
+    limiter.acquire(url.host_str().unwrap_or("")).await;
+
+    let mut request = client.get(url.as_str());
+    if let Some(entry) = revalidate {
+        if let Some(etag) = &entry.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| anyhow!("Network error: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(FetchOutcome::NotModified);
+    }
+
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "HTTP error: {} - {}",
+            response.status(),
+            response.status().canonical_reason().unwrap_or("Unknown")
+        ));
+    }
+
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let last_modified = response
+        .headers()
+        .get(reqwest::header::LAST_MODIFIED)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| anyhow!("Failed to read response body: {}", e))?;
+
+    Ok(FetchOutcome::Fetched { body, etag, last_modified })
+}
@@ -0,0 +1,173 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use scraper::{Html, Selector};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+use tokio::time::sleep;
+use url::Url;
+
+use crate::cache;
+use crate::domain_filter;
+use crate::extractors;
+use crate::ratelimit::RateLimiter;
+use crate::robots::{self, RobotsRules};
+use crate::{extract_elements, ScrapedData, ScraperConfig};
+
+/// Crawl starting from `config.url`, following discovered links breadth-first
+/// and filtering each one through `config.allow_domains`/`block_domains`/
+/// `same_host_only` before enqueuing it.
+///
+/// Stops once `config.max_depth` is exceeded or `config.max_pages` pages have
+/// been fetched, whichever comes first. Each page is run through
+/// `config.extractor` first, falling back to the generic selector the same
+/// way `scrape_single` does; returns one `ScrapedData` entry per page that
+/// produced a result.
+pub async fn crawl(
+    config: &ScraperConfig,
+    client: &Client,
+    limiter: &RateLimiter,
+) -> Result<Vec<ScrapedData>> {
+    let start_url = Url::parse(&config.url)
+        .map_err(|_| anyhow!("Invalid URL format: {}", config.url))?;
+    let start_host = start_url
+        .host_str()
+        .ok_or_else(|| anyhow!("URL has no host: {}", config.url))?
+        .to_string();
+
+    let selector = Selector::parse(&config.selector)
+        .map_err(|_| anyhow!("Invalid CSS selector: {}", config.selector))?;
+
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<(Url, usize)> = VecDeque::new();
+    queue.push_back((start_url, 0));
+
+    // Per-host robots.txt rules, fetched lazily the first time a host is seen
+    // so we never re-request the same robots.txt twice in one crawl.
+    let mut robots_cache: HashMap<String, RobotsRules> = HashMap::new();
+
+    // Reuse the same on-disk cache `scrape_single` uses, so pages revisited
+    // across a crawl (or linked to more than once) don't get re-downloaded.
+    let page_cache = config
+        .cache_dir
+        .as_ref()
+        .map(|dir| cache::Cache::new(dir.clone(), config.cache_ttl_secs));
+
+    let mut pages = Vec::new();
+    // Counts every page actually fetched, regardless of whether its selector
+    // matched anything - `pages` only grows on a match, so it can't be used
+    // to bound the number of requests or space out delays between them.
+    let mut fetched = 0usize;
+
+    while let Some((url, depth)) = queue.pop_front() {
+        if visited.contains(url.as_str()) {
+            continue;
+        }
+        if fetched >= config.max_pages {
+            break;
+        }
+        visited.insert(url.as_str().to_string());
+
+        let mut delay_ms = config.delay_ms;
+        if config.respect_robots {
+            let host = url.host_str().unwrap_or("").to_string();
+            let rules = match robots_cache.get(&host) {
+                Some(rules) => rules,
+                None => {
+                    let rules = robots::fetch_rules(client, &url, &config.user_agent).await;
+                    robots_cache.entry(host.clone()).or_insert(rules)
+                }
+            };
+            if !rules.can_fetch(url.path()) {
+                eprintln!("Skipping {} (disallowed by robots.txt)", url);
+                continue;
+            }
+            if let Some(crawl_delay) = rules.crawl_delay {
+                delay_ms = delay_ms.max((crawl_delay * 1000.0).round() as u64);
+            }
+        }
+
+        if delay_ms > 0 && fetched > 0 {
+            sleep(Duration::from_millis(delay_ms)).await;
+        }
+
+        println!("Fetching ({}/{}, depth {}): {}", fetched + 1, config.max_pages, depth, url);
+        let html_content = match cache::fetch_cached(client, &url, page_cache.as_ref(), limiter).await {
+            Ok(body) => body,
+            Err(e) => {
+                eprintln!("Warning: failed to fetch {}: {}", url, e);
+                continue;
+            }
+        };
+        fetched += 1;
+
+        let document = Html::parse_document(&html_content);
+
+        if depth < config.max_depth {
+            for link_url in discover_links(&document, &url) {
+                let Some(link_host) = link_url.host_str() else {
+                    continue;
+                };
+                let allowed = domain_filter::is_allowed(
+                    link_host,
+                    &start_host,
+                    &config.allow_domains,
+                    &config.block_domains,
+                    config.same_host_only,
+                );
+                if allowed && !visited.contains(link_url.as_str()) {
+                    queue.push_back((link_url, depth + 1));
+                }
+            }
+        }
+
+        // Prefer a site-specific extractor over the generic selector path,
+        // the same precedence `scrape_single` uses.
+        let registered = extractors::registry();
+        if let Some(extractor) = extractors::resolve(&registered, &config.extractor, &url) {
+            match extractor.extract(&document, &url) {
+                Ok(extracted) => pages.push(ScrapedData {
+                    url: url.to_string(),
+                    selector: format!("extractor:{}", extractor.name()),
+                    results: Vec::new(),
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    extracted: Some(extracted),
+                }),
+                Err(e) => {
+                    eprintln!("Warning: extractor '{}' failed on {}: {}", extractor.name(), url, e)
+                }
+            }
+            continue;
+        }
+
+        let results = extract_elements(&document, &selector);
+        if !results.is_empty() {
+            pages.push(ScrapedData {
+                url: url.to_string(),
+                selector: config.selector.clone(),
+                results,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                extracted: None,
+            });
+        }
+    }
+
+    if pages.is_empty() {
+        return Err(anyhow!(
+            "Crawl of {} found no elements matching selector '{}'",
+            config.url,
+            config.selector
+        ));
+    }
+
+    Ok(pages)
+}
+
+/// Extract and resolve every `href` on the page into an absolute `Url`.
+fn discover_links(document: &Html, base: &Url) -> Vec<Url> {
+    let link_selector = Selector::parse("a[href]").expect("static selector is valid");
+    document
+        .select(&link_selector)
+        .filter_map(|el| el.value().attr("href"))
+        .filter_map(|href| base.join(href).ok())
+        .collect()
+}
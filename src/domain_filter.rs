@@ -0,0 +1,76 @@
+/// Decide whether a link discovered during a crawl should be followed, based
+/// on the user's `--allow-domain`/`--block-domain`/`--same-host-only` flags.
+///
+/// The crawler stays on the starting host by default, just like it always
+/// has — a non-empty `--allow-domain` list is the only way to widen it past
+/// that host, and it replaces that default: only hosts matching the list are
+/// followed, the starting host included. `--same-host-only` pins it to the starting host even when an
+/// allowlist is also given. Any host matching the blocklist is always
+/// skipped, regardless of the other two.
+pub fn is_allowed(
+    host: &str,
+    start_host: &str,
+    allow_domains: &[String],
+    block_domains: &[String],
+    same_host_only: bool,
+) -> bool {
+    if block_domains.iter().any(|pattern| domain_matches(host, pattern)) {
+        return false;
+    }
+    if same_host_only {
+        return host == start_host;
+    }
+    if !allow_domains.is_empty() {
+        return allow_domains.iter().any(|pattern| domain_matches(host, pattern));
+    }
+    host == start_host
+}
+
+/// Whether `host` matches `pattern`, where `pattern` may be a bare domain
+/// (`example.com`) or a wildcard subdomain form (`*.example.com`) — both
+/// match the domain itself and any of its subdomains.
+fn domain_matches(host: &str, pattern: &str) -> bool {
+    let base = pattern.strip_prefix("*.").unwrap_or(pattern);
+    host == base || host.ends_with(&format!(".{base}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn domain_matches_exact_and_subdomains() {
+        assert!(domain_matches("example.com", "example.com"));
+        assert!(domain_matches("www.example.com", "*.example.com"));
+        assert!(domain_matches("example.com", "*.example.com"));
+        assert!(!domain_matches("evilexample.com", "example.com"));
+        assert!(!domain_matches("example.org", "example.com"));
+    }
+
+    #[test]
+    fn default_stays_on_starting_host() {
+        assert!(is_allowed("example.com", "example.com", &[], &[], false));
+        assert!(!is_allowed("other.com", "example.com", &[], &[], false));
+    }
+
+    #[test]
+    fn allow_domain_widens_past_the_starting_host() {
+        let allow = vec!["other.com".to_string()];
+        assert!(is_allowed("other.com", "example.com", &allow, &[], false));
+        assert!(!is_allowed("third.com", "example.com", &allow, &[], false));
+    }
+
+    #[test]
+    fn block_domain_always_wins() {
+        let allow = vec!["other.com".to_string()];
+        let block = vec!["other.com".to_string()];
+        assert!(!is_allowed("other.com", "example.com", &allow, &block, false));
+    }
+
+    #[test]
+    fn same_host_only_overrides_allow_domains() {
+        let allow = vec!["other.com".to_string()];
+        assert!(!is_allowed("other.com", "example.com", &allow, &[], true));
+        assert!(is_allowed("example.com", "example.com", &allow, &[], true));
+    }
+}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+use scraper::{Html, Selector};
+use serde_json::json;
+use url::Url;
+
+use super::{first_attr, first_text, meta_content, Extractor};
+
+/// Extracts title/author/date/body from pages that look like articles or
+/// blog posts, using the `<meta>` tags and semantic elements most publishing
+/// platforms already emit.
+pub struct ArticleExtractor;
+
+impl Extractor for ArticleExtractor {
+    fn name(&self) -> &'static str {
+        "article"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let path = url.path().to_lowercase();
+        ["/article", "/articles", "/blog", "/news", "/post", "/posts"]
+            .iter()
+            .any(|prefix| path.contains(prefix))
+    }
+
+    fn extract(&self, document: &Html, url: &Url) -> Result<serde_json::Value> {
+        let title = first_text(document, "h1")
+            .or_else(|| meta_content(document, "og:title"))
+            .unwrap_or_default();
+        let author = meta_content(document, "author")
+            .or_else(|| first_text(document, "[rel=author], .author, .byline"))
+            .unwrap_or_default();
+        let date = meta_content(document, "article:published_time")
+            .or_else(|| first_attr(document, "time", "datetime"))
+            .unwrap_or_default();
+        let body = document
+            .select(&Selector::parse("article p, .article-body p, .post-content p").unwrap())
+            .map(|el| el.text().collect::<Vec<_>>().join(" ").trim().to_string())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Ok(json!({
+            "type": "article",
+            "url": url.as_str(),
+            "title": title,
+            "author": author,
+            "date": date,
+            "body": body,
+        }))
+    }
+}
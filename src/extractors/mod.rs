@@ -0,0 +1,85 @@
+mod article;
+mod product;
+
+use anyhow::Result;
+use scraper::Html;
+use url::Url;
+
+pub use article::ArticleExtractor;
+pub use product::ProductExtractor;
+
+/// A site-specific scraper that turns a parsed `Html` document into a
+/// structured `serde_json::Value`, modeled after the "yt-dlp for websites"
+/// idea: each extractor knows which URLs it handles and what shape of data
+/// to pull out of them.
+pub trait Extractor: Send + Sync {
+    /// Short, stable name used to select this extractor with `--extractor`.
+    fn name(&self) -> &'static str;
+
+    /// Whether this extractor knows how to handle `url`.
+    fn matches(&self, url: &Url) -> bool;
+
+    /// Produce a structured record for `document`, fetched from `url`.
+    fn extract(&self, document: &Html, url: &Url) -> Result<serde_json::Value>;
+}
+
+/// All extractors available to the CLI, tried in order for `auto` mode.
+pub fn registry() -> Vec<Box<dyn Extractor>> {
+    vec![Box::new(ArticleExtractor), Box::new(ProductExtractor)]
+}
+
+/// Resolve the `--extractor` flag to a concrete extractor for this page.
+///
+/// * `"generic"` always falls back to the CSS-selector path (returns `None`).
+/// * `"auto"` picks the first registered extractor whose `matches` agrees.
+/// * Anything else is treated as an extractor name and must match by name
+///   (regardless of `matches`), so users can force a specific extractor.
+pub fn resolve<'a>(
+    extractors: &'a [Box<dyn Extractor>],
+    mode: &str,
+    url: &Url,
+) -> Option<&'a dyn Extractor> {
+    match mode {
+        "generic" => None,
+        "auto" => extractors
+            .iter()
+            .find(|e| e.matches(url))
+            .map(|e| e.as_ref()),
+        name => extractors
+            .iter()
+            .find(|e| e.name() == name)
+            .map(|e| e.as_ref()),
+    }
+}
+
+/// Text of the first element matching `selector`, trimmed.
+fn first_text(document: &Html, selector: &str) -> Option<String> {
+    let selector = scraper::Selector::parse(selector).ok()?;
+    document
+        .select(&selector)
+        .next()
+        .map(|el| el.text().collect::<Vec<_>>().join(" ").trim().to_string())
+}
+
+/// Value of `attr` on the first element matching `selector`.
+fn first_attr(document: &Html, selector: &str, attr: &str) -> Option<String> {
+    let selector = scraper::Selector::parse(selector).ok()?;
+    document
+        .select(&selector)
+        .next()
+        .and_then(|el| el.value().attr(attr))
+        .map(|value| value.to_string())
+}
+
+/// Content of the first `<meta name="NAME">` or `<meta property="NAME">` tag.
+fn meta_content(document: &Html, name: &str) -> Option<String> {
+    let selector = scraper::Selector::parse(&format!(
+        "meta[name='{name}'], meta[property='{name}']"
+    ))
+    .ok()?;
+    document
+        .select(&selector)
+        .next()
+        .and_then(|el| el.value().attr("content"))
+        .map(|value| value.to_string())
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use scraper::Html;
+use serde_json::json;
+use url::Url;
+
+use super::{first_attr, first_text, meta_content, Extractor};
+
+/// Extracts name/price/availability from e-commerce product pages, preferring
+/// `og:` and schema.org `itemprop` markup where present and falling back to
+/// common class-name conventions otherwise.
+pub struct ProductExtractor;
+
+impl Extractor for ProductExtractor {
+    fn name(&self) -> &'static str {
+        "product"
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let path = url.path().to_lowercase();
+        ["/product", "/products", "/item", "/items", "/shop", "/p/"]
+            .iter()
+            .any(|prefix| path.contains(prefix))
+    }
+
+    fn extract(&self, document: &Html, url: &Url) -> Result<serde_json::Value> {
+        let name = meta_content(document, "og:title")
+            .or_else(|| first_text(document, "[itemprop=name], h1"))
+            .unwrap_or_default();
+        let price = first_attr(document, "[itemprop=price]", "content")
+            .or_else(|| first_text(document, "[itemprop=price], .price"))
+            .unwrap_or_default();
+        let availability = first_attr(document, "[itemprop=availability]", "content")
+            .or_else(|| first_text(document, ".availability, .stock-status"))
+            .unwrap_or_default();
+
+        Ok(json!({
+            "type": "product",
+            "url": url.as_str(),
+            "name": name,
+            "price": price,
+            "availability": availability,
+        }))
+    }
+}
@@ -1,3 +1,11 @@
+mod cache;
+mod crawler;
+mod domain_filter;
+mod extractors;
+mod ratelimit;
+mod robots;
+mod server;
+
 use anyhow::{anyhow, Result};
 use clap::{Arg, Command};
 use reqwest::Client;
@@ -8,12 +16,17 @@ use std::time::Duration;
 use tokio::time::sleep;
 use url::Url;
 
+use ratelimit::RateLimiter;
+use robots::RobotsRules;
+
 #[derive(Serialize)]
 struct ScrapedData {
     url: String,
     selector: String,
     results: Vec<ScrapedElement>,
     timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extracted: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -22,6 +35,31 @@ struct ScrapedElement {
     attributes: HashMap<String, String>,
 }
 
+/// Distinguishes the handful of `scrape_single` failure modes that callers
+/// (namely the `serve` HTTP endpoint) need to react to differently, without
+/// coupling that to the exact wording of the error message.
+#[derive(Debug)]
+enum ScrapeError {
+    InvalidUrl(String),
+    InvalidSelector(String),
+    NoMatches { selector: String, url: String },
+}
+
+impl std::fmt::Display for ScrapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScrapeError::InvalidUrl(url) => write!(f, "Invalid URL format: {url}"),
+            ScrapeError::InvalidSelector(selector) => write!(f, "Invalid CSS selector: {selector}"),
+            ScrapeError::NoMatches { selector, url } => write!(
+                f,
+                "No elements found matching selector '{selector}' on {url}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScrapeError {}
+
 struct ScraperConfig {
     url: String,
     selector: String,
@@ -29,6 +67,17 @@ struct ScraperConfig {
     delay_ms: u64,
     user_agent: String,
     respect_robots: bool,
+    crawl: bool,
+    max_depth: usize,
+    max_pages: usize,
+    extractor: String,
+    rate_burst: f64,
+    rate_per_sec: f64,
+    cache_dir: Option<std::path::PathBuf>,
+    cache_ttl_secs: u64,
+    allow_domains: Vec<String>,
+    block_domains: Vec<String>,
+    same_host_only: bool,
 }
 
 #[tokio::main]
@@ -36,6 +85,18 @@ async fn main() -> Result<()> {
     let matches = Command::new("webscraper")
         .version("0.1.0")
         .about("A flexible web scraper with CSS selector support")
+        .subcommand_negates_reqs(true)
+        .subcommand(
+            Command::new("serve")
+                .about("Expose scraping as a JSON HTTP API")
+                .arg(
+                    Arg::new("port")
+                        .long("port")
+                        .value_name("PORT")
+                        .help("Port to listen on")
+                        .default_value("8080"),
+                ),
+        )
         .arg(
             Arg::new("url")
                 .short('u')
@@ -81,8 +142,102 @@ async fn main() -> Result<()> {
                 .help("Ignore robots.txt rules")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("crawl")
+                .long("crawl")
+                .help("Crawl mode: follow links instead of scraping a single page")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("max-depth")
+                .long("max-depth")
+                .value_name("N")
+                .help("Maximum link depth to follow in crawl mode")
+                .default_value("2"),
+        )
+        .arg(
+            Arg::new("max-pages")
+                .long("max-pages")
+                .value_name("M")
+                .help("Maximum number of pages to fetch in crawl mode")
+                .default_value("20"),
+        )
+        .arg(
+            Arg::new("extractor")
+                .long("extractor")
+                .value_name("NAME")
+                .help("Extractor to use: 'auto', 'generic', or a registered extractor name")
+                .default_value("auto"),
+        )
+        .arg(
+            Arg::new("rate-burst")
+                .long("rate-burst")
+                .value_name("TOKENS")
+                .help("Per-host token bucket size")
+                .default_value("5"),
+        )
+        .arg(
+            Arg::new("rate-per-sec")
+                .long("rate-per-sec")
+                .value_name("TOKENS_PER_SEC")
+                .help("Per-host token bucket refill rate")
+                .default_value("2.0"),
+        )
+        .arg(
+            Arg::new("cache-dir")
+                .long("cache-dir")
+                .value_name("PATH")
+                .help("Directory to cache fetched pages in (disabled unless set)"),
+        )
+        .arg(
+            Arg::new("cache-ttl")
+                .long("cache-ttl")
+                .value_name("SECONDS")
+                .help("How long a cached page stays fresh before revalidation")
+                .default_value("3600"),
+        )
+        .arg(
+            Arg::new("allow-domain")
+                .long("allow-domain")
+                .value_name("DOMAIN")
+                .help("Only follow links to this domain (and its subdomains) in crawl mode; repeatable")
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("block-domain")
+                .long("block-domain")
+                .value_name("DOMAIN")
+                .help("Never follow links to this domain (and its subdomains) in crawl mode; repeatable")
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("same-host-only")
+                .long("same-host-only")
+                .help("Restrict crawl mode to the starting page's exact host")
+                .action(clap::ArgAction::SetTrue),
+        )
         .get_matches();
 
+    let rate_burst: f64 = matches
+        .get_one::<String>("rate-burst")
+        .unwrap()
+        .parse()
+        .unwrap_or(5.0);
+    let rate_per_sec: f64 = matches
+        .get_one::<String>("rate-per-sec")
+        .unwrap()
+        .parse()
+        .unwrap_or(2.0);
+
+    if let Some(serve_matches) = matches.subcommand_matches("serve") {
+        let port: u16 = serve_matches
+            .get_one::<String>("port")
+            .unwrap()
+            .parse()
+            .map_err(|_| anyhow!("Invalid port"))?;
+        return server::serve(port, rate_burst, rate_per_sec).await;
+    }
+
     let config = ScraperConfig {
         url: matches.get_one::<String>("url").unwrap().clone(),
         selector: matches.get_one::<String>("selector").unwrap().clone(),
@@ -94,6 +249,37 @@ async fn main() -> Result<()> {
             .unwrap_or(1000),
         user_agent: matches.get_one::<String>("user-agent").unwrap().clone(),
         respect_robots: !matches.get_flag("ignore-robots"),
+        crawl: matches.get_flag("crawl"),
+        max_depth: matches
+            .get_one::<String>("max-depth")
+            .unwrap()
+            .parse()
+            .unwrap_or(2),
+        max_pages: matches
+            .get_one::<String>("max-pages")
+            .unwrap()
+            .parse()
+            .unwrap_or(20),
+        extractor: matches.get_one::<String>("extractor").unwrap().clone(),
+        rate_burst,
+        rate_per_sec,
+        cache_dir: matches
+            .get_one::<String>("cache-dir")
+            .map(std::path::PathBuf::from),
+        cache_ttl_secs: matches
+            .get_one::<String>("cache-ttl")
+            .unwrap()
+            .parse()
+            .unwrap_or(3600),
+        allow_domains: matches
+            .get_many::<String>("allow-domain")
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default(),
+        block_domains: matches
+            .get_many::<String>("block-domain")
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default(),
+        same_host_only: matches.get_flag("same-host-only"),
     };
 
     match scrape_website(&config).await {
@@ -109,78 +295,89 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn scrape_website(config: &ScraperConfig) -> Result<ScrapedData> {
+async fn scrape_website(config: &ScraperConfig) -> Result<Vec<ScrapedData>> {
+    // Create HTTP client once so connections are pooled across every request
+    // this run makes, whether that's a single page or a whole crawl.
+    let client = Client::builder()
+        .user_agent(&config.user_agent)
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let limiter = RateLimiter::new(config.rate_burst, config.rate_per_sec);
+
+    if config.crawl {
+        return crawler::crawl(config, &client, &limiter).await;
+    }
+
+    Ok(vec![scrape_single(config, &client, &limiter).await?])
+}
+
+/// Fetch and extract a single page. Shared by the plain CLI path and the
+/// `serve` HTTP endpoint, both of which supply their own pooled `Client` and
+/// `RateLimiter`.
+async fn scrape_single(
+    config: &ScraperConfig,
+    client: &Client,
+    limiter: &RateLimiter,
+) -> Result<ScrapedData> {
     // Validate URL
-    let parsed_url = Url::parse(&config.url)
-        .map_err(|_| anyhow!("Invalid URL format: {}", config.url))?;
+    let parsed_url =
+        Url::parse(&config.url).map_err(|_| ScrapeError::InvalidUrl(config.url.clone()))?;
 
-    // Check robots.txt if enabled
+    // Check robots.txt if enabled, raising the delay to match any Crawl-delay
+    // the site asks for.
+    let mut delay_ms = config.delay_ms;
     if config.respect_robots {
-        if let Err(e) = check_robots_txt(&parsed_url, &config.user_agent).await {
-            eprintln!("Warning: {}", e);
+        let rules = check_robots_txt(&parsed_url, &config.user_agent, client).await;
+        if let Some(crawl_delay) = rules.crawl_delay {
+            delay_ms = delay_ms.max((crawl_delay * 1000.0).round() as u64);
         }
     }
 
-    // Create HTTP client with custom User-Agent
-    let client = Client::builder()
-        .user_agent(&config.user_agent)
-        .timeout(Duration::from_secs(30))
-        .build()?;
-
     // Add delay before request
-    if config.delay_ms > 0 {
-        sleep(Duration::from_millis(config.delay_ms)).await;
+    if delay_ms > 0 {
+        sleep(Duration::from_millis(delay_ms)).await;
     }
 
-    // Fetch the webpage
+    // Fetch the webpage, reusing a cached copy when one is fresh or can be
+    // cheaply revalidated.
     println!("Fetching: {}", config.url);
-    let response = client
-        .get(&config.url)
-        .send()
-        .await
-        .map_err(|e| anyhow!("Network error: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(anyhow!(
-            "HTTP error: {} - {}",
-            response.status(),
-            response.status().canonical_reason().unwrap_or("Unknown")
-        ));
-    }
-
-    let html_content = response
-        .text()
-        .await
-        .map_err(|e| anyhow!("Failed to read response body: {}", e))?;
+    let page_cache = config
+        .cache_dir
+        .as_ref()
+        .map(|dir| cache::Cache::new(dir.clone(), config.cache_ttl_secs));
+    let html_content =
+        cache::fetch_cached(client, &parsed_url, page_cache.as_ref(), limiter).await?;
 
     // Parse HTML
     let document = Html::parse_document(&html_content);
 
+    // Prefer a site-specific extractor over the generic selector path, unless
+    // the user forced `--extractor generic`.
+    let registered = extractors::registry();
+    if let Some(extractor) = extractors::resolve(&registered, &config.extractor, &parsed_url) {
+        let extracted = extractor.extract(&document, &parsed_url)?;
+        return Ok(ScrapedData {
+            url: config.url.clone(),
+            selector: format!("extractor:{}", extractor.name()),
+            results: Vec::new(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            extracted: Some(extracted),
+        });
+    }
+
     // Parse CSS selector
     let selector = Selector::parse(&config.selector)
-        .map_err(|_| anyhow!("Invalid CSS selector: {}", config.selector))?;
+        .map_err(|_| ScrapeError::InvalidSelector(config.selector.clone()))?;
 
     // Extract elements
-    let mut results = Vec::new();
-    for element in document.select(&selector) {
-        let text = element.text().collect::<Vec<_>>().join(" ").trim().to_string();
-        
-        let mut attributes = HashMap::new();
-        for (name, value) in element.value().attrs() {
-            attributes.insert(name.to_string(), value.to_string());
-        }
-
-        if !text.is_empty() || !attributes.is_empty() {
-            results.push(ScrapedElement { text, attributes });
-        }
-    }
+    let results = extract_elements(&document, &selector);
 
     if results.is_empty() {
-        return Err(anyhow!(
-            "No elements found matching selector '{}' on {}",
-            config.selector,
-            config.url
-        ));
+        return Err(ScrapeError::NoMatches {
+            selector: config.selector.clone(),
+            url: config.url.clone(),
+        }
+        .into());
     }
 
     Ok(ScrapedData {
@@ -188,89 +385,83 @@ async fn scrape_website(config: &ScraperConfig) -> Result<ScrapedData> {
         selector: config.selector.clone(),
         results,
         timestamp: chrono::Utc::now().to_rfc3339(),
+        extracted: None,
     })
 }
 
-async fn check_robots_txt(url: &Url, user_agent: &str) -> Result<()> {
-    let robots_url = format!("{}://{}/robots.txt", url.scheme(), url.host_str().unwrap_or(""));
-    
-    let client = Client::builder()
-        .user_agent(user_agent)
-        .timeout(Duration::from_secs(10))
-        .build()?;
-
-    match client.get(&robots_url).send().await {
-        Ok(response) if response.status().is_success() => {
-            let robots_content = response.text().await?;
-            
-            // Simple robots.txt parsing - check for Disallow rules
-            let lines: Vec<&str> = robots_content.lines().collect();
-            let mut relevant_user_agent = false;
-            let mut disallowed_paths = Vec::new();
-
-            for line in lines {
-                let line = line.trim();
-                if line.starts_with("User-agent:") {
-                    let agent = line.split(':').nth(1).unwrap_or("").trim();
-                    relevant_user_agent = agent == "*" || agent.to_lowercase() == user_agent.to_lowercase();
-                } else if relevant_user_agent && line.starts_with("Disallow:") {
-                    let path = line.split(':').nth(1).unwrap_or("").trim();
-                    if !path.is_empty() {
-                        disallowed_paths.push(path);
-                    }
-                }
-            }
-
-            // Check if the current URL path is disallowed
-            let url_path = url.path();
-            for disallowed in &disallowed_paths {
-                if url_path.starts_with(disallowed) {
-                    return Err(anyhow!(
-                        "Access to {} is disallowed by robots.txt (rule: Disallow: {})",
-                        url_path,
-                        disallowed
-                    ));
-                }
-            }
+/// Pull text and attributes out of every element matching `selector`.
+fn extract_elements(document: &Html, selector: &Selector) -> Vec<ScrapedElement> {
+    let mut results = Vec::new();
+    for element in document.select(selector) {
+        let text = element.text().collect::<Vec<_>>().join(" ").trim().to_string();
 
-            println!("✓ robots.txt check passed");
-        }
-        Ok(_) => {
-            println!("⚠ robots.txt not found or inaccessible, proceeding anyway");
+        let mut attributes = HashMap::new();
+        for (name, value) in element.value().attrs() {
+            attributes.insert(name.to_string(), value.to_string());
         }
-        Err(_) => {
-            println!("⚠ Could not fetch robots.txt, proceeding anyway");
+
+        if !text.is_empty() || !attributes.is_empty() {
+            results.push(ScrapedElement { text, attributes });
         }
     }
+    results
+}
 
-    Ok(())
+/// Fetch and parse `robots.txt` for `url`'s host, warning (but not failing)
+/// when the current path is disallowed. Returns the parsed rules so the
+/// caller can also pick up `Crawl-delay`.
+async fn check_robots_txt(url: &Url, user_agent: &str, client: &Client) -> RobotsRules {
+    let rules = robots::fetch_rules(client, url, user_agent).await;
+
+    if !rules.can_fetch(url.path()) {
+        eprintln!(
+            "Warning: Access to {} is disallowed by robots.txt",
+            url.path()
+        );
+    } else {
+        println!("✓ robots.txt check passed");
+    }
+
+    if !rules.sitemaps.is_empty() {
+        println!("Sitemaps listed in robots.txt: {}", rules.sitemaps.join(", "));
+    }
+
+    rules
 }
 
-fn output_results(data: &ScrapedData, format: &str) -> Result<()> {
+fn output_results(pages: &[ScrapedData], format: &str) -> Result<()> {
     match format.to_lowercase().as_str() {
         "json" => {
-            let json_output = serde_json::to_string_pretty(data)?;
+            let json_output = serde_json::to_string_pretty(pages)?;
             println!("{}", json_output);
         }
         "text" | _ => {
-            println!("=== Web Scraping Results ===");
-            println!("URL: {}", data.url);
-            println!("Selector: {}", data.selector);
-            println!("Timestamp: {}", data.timestamp);
-            println!("Found {} element(s):\n", data.results.len());
-
-            for (i, element) in data.results.iter().enumerate() {
-                println!("--- Element {} ---", i + 1);
-                if !element.text.is_empty() {
-                    println!("Text: {}", element.text);
+            for data in pages {
+                println!("=== Web Scraping Results ===");
+                println!("URL: {}", data.url);
+                println!("Selector: {}", data.selector);
+                println!("Timestamp: {}", data.timestamp);
+
+                if let Some(extracted) = &data.extracted {
+                    println!("{}\n", serde_json::to_string_pretty(extracted)?);
+                    continue;
                 }
-                if !element.attributes.is_empty() {
-                    println!("Attributes:");
-                    for (key, value) in &element.attributes {
-                        println!("  {}: {}", key, value);
+
+                println!("Found {} element(s):\n", data.results.len());
+
+                for (i, element) in data.results.iter().enumerate() {
+                    println!("--- Element {} ---", i + 1);
+                    if !element.text.is_empty() {
+                        println!("Text: {}", element.text);
+                    }
+                    if !element.attributes.is_empty() {
+                        println!("Attributes:");
+                        for (key, value) in &element.attributes {
+                            println!("  {}: {}", key, value);
+                        }
                     }
+                    println!();
                 }
-                println!();
             }
         }
     }
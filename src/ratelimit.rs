@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::{sleep, Instant};
+
+/// A per-host token bucket: each host gets `burst` tokens that refill at
+/// `per_sec` tokens/second, computed lazily whenever that host is accessed.
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Rate limiter shared across crawl/server requests so one busy host can't
+/// starve requests to the others. Cheap to construct; a no-op when
+/// `per_sec` is zero or negative (limiting disabled).
+pub struct RateLimiter {
+    burst: f64,
+    per_sec: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(burst: f64, per_sec: f64) -> RateLimiter {
+        RateLimiter {
+            burst,
+            per_sec,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Block until a token is available for `host`, refilling lazily based
+    /// on elapsed time since the bucket was last touched.
+    pub async fn acquire(&self, host: &str) {
+        if self.per_sec <= 0.0 {
+            return;
+        }
+
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let bucket = buckets.entry(host.to_string()).or_insert_with(|| Bucket {
+                    tokens: self.burst,
+                    last_refill: Instant::now(),
+                });
+
+                let now = Instant::now();
+                let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+                bucket.tokens = (bucket.tokens + elapsed * self.per_sec).min(self.burst);
+                bucket.last_refill = now;
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    None
+                } else {
+                    let shortfall = 1.0 - bucket.tokens;
+                    Some(Duration::from_secs_f64(shortfall / self.per_sec))
+                }
+            };
+
+            match wait {
+                None => return,
+                Some(duration) => sleep(duration).await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn burst_tokens_are_consumed_without_waiting() {
+        let limiter = RateLimiter::new(2.0, 1.0);
+        let start = Instant::now();
+        limiter.acquire("host").await;
+        limiter.acquire("host").await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn empty_bucket_waits_for_the_refill_shortfall() {
+        let limiter = RateLimiter::new(1.0, 2.0);
+        limiter.acquire("host").await; // drains the single burst token
+
+        let start = Instant::now();
+        limiter.acquire("host").await; // needs a full token back at 2/sec
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn hosts_have_independent_buckets() {
+        let limiter = RateLimiter::new(1.0, 1.0);
+        limiter.acquire("a").await;
+
+        let start = Instant::now();
+        limiter.acquire("b").await; // untouched bucket, should not wait
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn disabled_when_per_sec_is_not_positive() {
+        let limiter = RateLimiter::new(0.0, 0.0);
+        let start = std::time::Instant::now();
+        for _ in 0..5 {
+            limiter.acquire("host").await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+}
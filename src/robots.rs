@@ -0,0 +1,258 @@
+use reqwest::Client;
+use url::Url;
+
+/// Parsed rules from a single `robots.txt` document, already narrowed down to
+/// the block that applies to one user agent (falling back to `*`).
+#[derive(Debug, Clone, Default)]
+pub struct RobotsRules {
+    rules: Vec<Rule>,
+    pub crawl_delay: Option<f64>,
+    pub sitemaps: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+impl RobotsRules {
+    /// Parse a `robots.txt` body, keeping only the rules that apply to
+    /// `user_agent` (the first group that names this exact agent,
+    /// case-insensitively, or `*` if none does). `Sitemap:` lines are global and always collected.
+    pub fn parse(body: &str, user_agent: &str) -> RobotsRules {
+        let user_agent = user_agent.to_lowercase();
+        let mut groups: Vec<(Vec<String>, Vec<Rule>, Option<f64>)> = Vec::new();
+        let mut sitemaps = Vec::new();
+
+        let mut current_agents: Vec<String> = Vec::new();
+        let mut current_rules: Vec<Rule> = Vec::new();
+        let mut current_delay: Option<f64> = None;
+
+        let flush = |groups: &mut Vec<(Vec<String>, Vec<Rule>, Option<f64>)>,
+                     agents: &mut Vec<String>,
+                     rules: &mut Vec<Rule>,
+                     delay: &mut Option<f64>| {
+            if !agents.is_empty() {
+                groups.push((
+                    std::mem::take(agents),
+                    std::mem::take(rules),
+                    delay.take(),
+                ));
+            }
+        };
+
+        for line in body.lines() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            match key.as_str() {
+                "user-agent" => {
+                    // A User-agent line seen after this group's rules have
+                    // already started means a new block is beginning.
+                    if !current_rules.is_empty() || current_delay.is_some() {
+                        flush(&mut groups, &mut current_agents, &mut current_rules, &mut current_delay);
+                    }
+                    current_agents.push(value.to_lowercase());
+                }
+                "disallow" => {
+                    if !value.is_empty() {
+                        current_rules.push(Rule { allow: false, pattern: value.to_string() });
+                    } else {
+                        // Empty Disallow means "allow everything".
+                        current_rules.push(Rule { allow: true, pattern: String::new() });
+                    }
+                }
+                "allow" => {
+                    current_rules.push(Rule { allow: true, pattern: value.to_string() });
+                }
+                "crawl-delay" => {
+                    current_delay = value.parse().ok();
+                }
+                "sitemap" => {
+                    sitemaps.push(value.to_string());
+                }
+                _ => {}
+            }
+        }
+        flush(&mut groups, &mut current_agents, &mut current_rules, &mut current_delay);
+
+        // Prefer the group that names this agent explicitly; fall back to `*`.
+        let named = groups.iter().find(|(agents, _, _)| agents.iter().any(|a| a == &user_agent));
+        let wildcard = groups.iter().find(|(agents, _, _)| agents.iter().any(|a| a == "*"));
+        let (rules, crawl_delay) = match named.or(wildcard) {
+            Some((_, rules, delay)) => (rules.clone(), *delay),
+            None => (Vec::new(), None),
+        };
+
+        RobotsRules { rules, crawl_delay, sitemaps }
+    }
+
+    /// Decide whether `path` may be fetched, using longest-match-wins
+    /// precedence across every matching `Allow`/`Disallow` rule.
+    pub fn can_fetch(&self, path: &str) -> bool {
+        let mut best: Option<&Rule> = None;
+        for rule in &self.rules {
+            if matches_pattern(&rule.pattern, path) {
+                let better = match best {
+                    None => true,
+                    Some(current) => rule.pattern.len() > current.pattern.len(),
+                };
+                if better {
+                    best = Some(rule);
+                }
+            }
+        }
+        best.map(|rule| rule.allow).unwrap_or(true)
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.find('#') {
+        Some(idx) => &line[..idx],
+        None => line,
+    }
+}
+
+/// Match a robots.txt path pattern against `path`, supporting `*` as a
+/// wildcard for any run of characters and `$` as an end-of-string anchor.
+fn matches_pattern(pattern: &str, path: &str) -> bool {
+    if pattern.is_empty() {
+        return true;
+    }
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(stripped) => (stripped, true),
+        None => (pattern, false),
+    };
+
+    let segments: Vec<&str> = pattern.split('*').collect();
+    let mut rest = path;
+
+    // The first segment must match at the very start of the path.
+    if let Some(first) = segments.first() {
+        if !rest.starts_with(*first) {
+            return false;
+        }
+        rest = &rest[first.len()..];
+    }
+
+    // Every remaining segment must appear later on, in order. There's no
+    // "middle" when there's only one segment (no wildcard at all), so guard
+    // the slice the same way the "last segment" case below already does.
+    if segments.len() > 1 {
+        for segment in &segments[1..segments.len() - 1] {
+            match rest.find(segment) {
+                Some(pos) => rest = &rest[pos + segment.len()..],
+                None => return false,
+            }
+        }
+    }
+
+    if segments.len() > 1 {
+        let last = segments[segments.len() - 1];
+        if anchored {
+            if !rest.ends_with(last) {
+                return false;
+            }
+        } else if !rest.contains(last) {
+            return false;
+        }
+    } else if anchored {
+        return rest.is_empty();
+    }
+
+    true
+}
+
+/// Fetch and parse `robots.txt` for `url`'s host. Returns permissive, empty
+/// rules if the document is missing or can't be read, matching the spirit of
+/// the spec: absence of `robots.txt` means "proceed anyway".
+pub async fn fetch_rules(client: &Client, url: &Url, user_agent: &str) -> RobotsRules {
+    let robots_url = format!(
+        "{}://{}/robots.txt",
+        url.scheme(),
+        url.host_str().unwrap_or("")
+    );
+
+    match client.get(&robots_url).send().await {
+        Ok(response) if response.status().is_success() => match response.text().await {
+            Ok(body) => RobotsRules::parse(&body, user_agent),
+            Err(_) => RobotsRules::default(),
+        },
+        _ => RobotsRules::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_disallow_with_no_wildcard_does_not_panic() {
+        let rules = RobotsRules::parse("User-agent: *\nDisallow: /private", "bot");
+        assert!(!rules.can_fetch("/private/x"));
+        assert!(rules.can_fetch("/public"));
+    }
+
+    #[test]
+    fn wildcard_and_end_anchor() {
+        let rules = RobotsRules::parse("User-agent: *\nDisallow: /*.pdf$", "bot");
+        assert!(!rules.can_fetch("/files/report.pdf"));
+        assert!(rules.can_fetch("/files/report.pdf.html"));
+    }
+
+    #[test]
+    fn longest_match_wins() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow: /\nAllow: /public",
+            "bot",
+        );
+        assert!(!rules.can_fetch("/private"));
+        assert!(rules.can_fetch("/public/page"));
+    }
+
+    #[test]
+    fn named_agent_group_preferred_over_wildcard() {
+        let body = "User-agent: *\nDisallow: /\nUser-agent: bot\nDisallow: /only-this";
+        let rules = RobotsRules::parse(body, "bot");
+        assert!(rules.can_fetch("/anything"));
+        assert!(!rules.can_fetch("/only-this"));
+    }
+
+    #[test]
+    fn distinct_groups_separated_by_rules_are_not_merged() {
+        let body = "\
+User-agent: a\n\
+Disallow: /a-only\n\
+User-agent: b\n\
+Disallow: /b-only";
+        let rules_a = RobotsRules::parse(body, "a");
+        assert!(!rules_a.can_fetch("/a-only"));
+        assert!(rules_a.can_fetch("/b-only"));
+
+        let rules_b = RobotsRules::parse(body, "b");
+        assert!(rules_b.can_fetch("/a-only"));
+        assert!(!rules_b.can_fetch("/b-only"));
+    }
+
+    #[test]
+    fn crawl_delay_and_sitemaps_are_collected() {
+        let body = "User-agent: *\nCrawl-delay: 5\nSitemap: https://example.com/sitemap.xml";
+        let rules = RobotsRules::parse(body, "bot");
+        assert_eq!(rules.crawl_delay, Some(5.0));
+        assert_eq!(rules.sitemaps, vec!["https://example.com/sitemap.xml".to_string()]);
+    }
+
+    #[test]
+    fn missing_robots_txt_is_permissive() {
+        let rules = RobotsRules::default();
+        assert!(rules.can_fetch("/anything"));
+    }
+}
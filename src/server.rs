@@ -0,0 +1,111 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Json};
+use axum::routing::get;
+use axum::Router;
+use reqwest::Client;
+use serde::Deserialize;
+use tokio::net::TcpListener;
+
+use crate::ratelimit::RateLimiter;
+use crate::{scrape_single, ScrapeError, ScraperConfig};
+
+#[derive(Clone)]
+struct AppState {
+    client: Arc<Client>,
+    limiter: Arc<RateLimiter>,
+}
+
+#[derive(Deserialize)]
+struct ScrapeQuery {
+    url: String,
+    selector: Option<String>,
+    format: Option<String>,
+}
+
+/// Start the `serve` subcommand: a long-running HTTP API wrapping
+/// `scrape_single`, reusing one pooled `Client` across every request.
+pub async fn serve(port: u16, rate_burst: f64, rate_per_sec: f64) -> Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let client = Client::builder()
+        .user_agent("webscraper/0.1.0 (Rust)")
+        .timeout(Duration::from_secs(30))
+        .build()?;
+
+    let state = AppState {
+        client: Arc::new(client),
+        limiter: Arc::new(RateLimiter::new(rate_burst, rate_per_sec)),
+    };
+
+    let app = Router::new()
+        .route("/scrape", get(handle_scrape))
+        .with_state(state);
+
+    let addr = format!("0.0.0.0:{port}");
+    let listener = TcpListener::bind(&addr).await?;
+    println!("Listening on http://{addr}");
+
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+async fn handle_scrape(
+    State(state): State<AppState>,
+    Query(params): Query<ScrapeQuery>,
+) -> impl IntoResponse {
+    let started = Instant::now();
+    let method = "GET";
+
+    let config = ScraperConfig {
+        url: params.url.clone(),
+        selector: params.selector.clone().unwrap_or_default(),
+        output_format: params.format.clone().unwrap_or_else(|| "json".to_string()),
+        delay_ms: 0,
+        user_agent: "webscraper/0.1.0 (Rust)".to_string(),
+        respect_robots: true,
+        crawl: false,
+        max_depth: 0,
+        max_pages: 1,
+        extractor: "auto".to_string(),
+        rate_burst: 0.0,
+        rate_per_sec: 0.0,
+        cache_dir: None,
+        cache_ttl_secs: 0,
+        allow_domains: Vec::new(),
+        block_domains: Vec::new(),
+        same_host_only: false,
+    };
+
+    let (status, body) = match scrape_single(&config, &state.client, &state.limiter).await {
+        Ok(data) => (StatusCode::OK, serde_json::to_value(&data).unwrap_or_default()),
+        Err(e) => {
+            // Match on the concrete error type rather than the message text,
+            // so this can't silently drift out of sync with how
+            // `scrape_single` actually fails.
+            let status = match e.downcast_ref::<ScrapeError>() {
+                Some(ScrapeError::InvalidUrl(_) | ScrapeError::InvalidSelector(_)) => {
+                    StatusCode::BAD_REQUEST
+                }
+                Some(ScrapeError::NoMatches { .. }) => StatusCode::NOT_FOUND,
+                None => StatusCode::BAD_GATEWAY,
+            };
+            (status, serde_json::json!({ "error": e.to_string() }))
+        }
+    };
+
+    tracing::info!(
+        method,
+        url = %params.url,
+        status = status.as_u16(),
+        elapsed_ms = %started.elapsed().as_millis(),
+        "handled scrape request"
+    );
+
+    (status, Json(body))
+}